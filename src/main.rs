@@ -1,4 +1,7 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::io::{self, Read};
+use std::rc::Rc;
 use std::str::FromStr;
 
 #[derive(PartialEq, Clone, Debug)]
@@ -8,13 +11,57 @@ enum Token {
     Ident(String),
     Int(i64),
     Float(f64),
+    Str(String),
+}
+
+/// The source region a token (or error) was lexed from. `start`/`end` are
+/// byte offsets into the input; `line`/`col` point at the first byte and are
+/// 1-based, so a caller can render an underlined snippet of the offending line.
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct Span {
+    start: usize,
+    end: usize,
+    line: usize,
+    col: usize,
+}
+
+/// The position of a single byte as it is pulled from the input.
+#[derive(Clone, Copy)]
+struct Pos {
+    byte: usize,
+    line: usize,
+    col: usize,
+}
+
+/// A lexed token (or error) together with the source span it covers.
+#[derive(PartialEq, Clone, Debug)]
+struct Spanned {
+    result: Result<Token, String>,
+    span: Span,
+}
+
+impl Spanned {
+    fn new(result: Result<Token, String>, start: Pos, end: usize) -> Self {
+        Self {
+            result,
+            span: Span {
+                start: start.byte,
+                end,
+                line: start.line,
+                col: start.col,
+            },
+        }
+    }
 }
 
 type BoxIter = Box<dyn Iterator<Item = u8>>;
 
 struct TokenIterator {
     it: BoxIter,
-    ungotten: Option<u8>,
+    ungotten: Option<(u8, Pos)>,
+    byte: usize,
+    line: usize,
+    col: usize,
 }
 
 impl TokenIterator {
@@ -25,6 +72,33 @@ impl TokenIterator {
     fn from_str(s: &'static str) -> Self {
         Self::from(s.bytes())
     }
+
+    /// Pull the next byte, tagged with its source position. Bytes pushed back
+    /// via [`Self::unget`] keep the position they were originally read at.
+    fn read(&mut self) -> Option<(u8, Pos)> {
+        if let Some(x) = self.ungotten.take() {
+            return Some(x);
+        }
+        let byte = self.it.next()?;
+        let pos = Pos {
+            byte: self.byte,
+            line: self.line,
+            col: self.col,
+        };
+        self.byte += 1;
+        if byte == b'\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some((byte, pos))
+    }
+
+    fn unget(&mut self, byte: u8, pos: Pos) {
+        assert!(self.ungotten.is_none());
+        self.ungotten = Some((byte, pos));
+    }
 }
 
 impl<T: Iterator<Item = u8> + 'static> From<T> for TokenIterator {
@@ -32,46 +106,395 @@ impl<T: Iterator<Item = u8> + 'static> From<T> for TokenIterator {
         Self {
             it: Box::new(iter.chain([b' '])),
             ungotten: None,
+            byte: 0,
+            line: 1,
+            col: 1,
         }
     }
 }
 
 impl Iterator for TokenIterator {
-    type Item = Result<Token, String>;
+    type Item = Spanned;
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut state = State::Start;
         let mut partial = String::new();
-        while let Some(byte) = self.ungotten.take().or_else(|| self.it.next()) {
+        let mut start: Option<Pos> = None;
+        let mut end = 0;
+        while let Some((byte, pos)) = self.read() {
             let ch = byte as char;
             match (state, ch) {
                 (State::Start, ' ' | '\n' | '\t' | '\r') => continue,
-                (State::Start, '(') => return Some(Ok(Token::LParen)),
-                (State::Start, ')') => return Some(Ok(Token::RParen)),
-                (State::Start, 'a'..='z' | 'A'..='Z' | '_') => state = State::Ident,
+                (State::Start, ';') => {
+                    state = State::Comment;
+                    continue;
+                }
+                (State::Comment, '\n') => {
+                    state = State::Start;
+                    continue;
+                }
+                (State::Comment, _) => continue,
+                (State::Start, '(') => {
+                    return Some(Spanned::new(Ok(Token::LParen), pos, pos.byte + 1))
+                }
+                (State::Start, ')') => {
+                    return Some(Spanned::new(Ok(Token::RParen), pos, pos.byte + 1))
+                }
+                (State::Start, '"') => {
+                    start = Some(pos);
+                    end = pos.byte + 1;
+                    state = State::String;
+                    continue;
+                }
+                (State::String, '"') => {
+                    return Some(Spanned::new(Ok(Token::Str(partial)), start.unwrap(), pos.byte + 1))
+                }
+                (State::String, '\\') => {
+                    end = pos.byte + 1;
+                    state = State::StringEscape;
+                    continue;
+                }
+                (State::String, _) => (),
+                (State::StringEscape, _) => {
+                    partial.push(match ch {
+                        '"' => '"',
+                        '\\' => '\\',
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        other => other,
+                    });
+                    end = pos.byte + 1;
+                    state = State::String;
+                    continue;
+                }
+                (State::Start, 'a'..='z' | 'A'..='Z' | '_' | '+' | '*' | '/') => {
+                    state = State::Ident
+                }
+                (State::Start, '-') => state = State::Minus,
                 (State::Start, '0'..='9') => state = State::Int,
+                (State::Minus, '0'..='9') => state = State::Int,
+                (State::Minus, '.') => state = State::Float,
                 (State::Start | State::Int, '.') => state = State::Float,
                 (State::Float, '.') |
                 (State::Int | State::Float, 'a'..='z' | 'A'..='Z') => state = State::Error,
                 (State::Int | State::Float, '0'..='9') |
-                (State::Ident | State::Error, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_') => (),
+                (State::Ident | State::Error, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_') |
+                (State::Ident, '+' | '*' | '/') => (),
                 _ => {
+                    let span_start = start.unwrap_or(pos);
                     if state != State::Start {
-                        assert!(self.ungotten.is_none());
-                        self.ungotten = Some(byte);
+                        self.unget(byte, pos);
+                    } else {
+                        end = pos.byte + 1;
                     }
-                    return match state {
-                        State::Start => Some(Err(ch.to_string())),
-                        State::Ident => Some(Ok(Token::Ident(partial))),
-                        State::Int => Some(Ok(Token::Int(i64::from_str(&partial).unwrap()))),
-                        State::Float => Some(Ok(Token::Float(f64::from_str(&partial).unwrap()))),
-                        State::Error => Some(Err(partial)),
+                    let result = match state {
+                        State::Start => Err(ch.to_string()),
+                        State::Ident | State::Minus => Ok(Token::Ident(partial)),
+                        State::Int => Ok(Token::Int(i64::from_str(&partial).unwrap())),
+                        State::Float => Ok(Token::Float(f64::from_str(&partial).unwrap())),
+                        State::Error => Err(partial),
+                        State::Comment | State::String | State::StringEscape => unreachable!(),
                     };
+                    return Some(Spanned::new(result, span_start, end));
                 }
             }
+            if start.is_none() {
+                start = Some(pos);
+            }
+            end = pos.byte + 1;
             partial.push(ch)
         }
-        None
+        match state {
+            State::String | State::StringEscape => {
+                let span_start = start.unwrap_or(Pos {
+                    byte: self.byte,
+                    line: self.line,
+                    col: self.col,
+                });
+                Some(Spanned::new(
+                    Err(format!("unterminated string literal: \"{partial}")),
+                    span_start,
+                    end,
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+enum Expr {
+    Atom(Token),
+    List(Vec<Expr>),
+}
+
+fn parse(it: &mut TokenIterator) -> Result<Expr, String> {
+    match it.next().map(|s| s.result) {
+        None => Err("unexpected end of input".to_string()),
+        Some(Err(e)) => Err(e),
+        Some(Ok(Token::RParen)) => Err("unexpected )".to_string()),
+        Some(Ok(Token::LParen)) => parse_list(it),
+        Some(Ok(tok)) => Ok(Expr::Atom(tok)),
+    }
+}
+
+fn parse_list(it: &mut TokenIterator) -> Result<Expr, String> {
+    let mut items = Vec::new();
+    loop {
+        match it.next().map(|s| s.result) {
+            None => return Err("unexpected end of input: expected )".to_string()),
+            Some(Err(e)) => return Err(e),
+            Some(Ok(Token::RParen)) => return Ok(Expr::List(items)),
+            Some(Ok(Token::LParen)) => items.push(parse_list(it)?),
+            Some(Ok(tok)) => items.push(Expr::Atom(tok)),
+        }
+    }
+}
+
+/// A runtime value produced by [`eval`].
+#[derive(PartialEq, Clone, Debug)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Nil,
+    Func(Func),
+}
+
+/// A user-defined closure: its parameters, body, and the environment it
+/// captured at the point of definition.
+#[derive(Clone)]
+struct Func {
+    params: Vec<String>,
+    body: Box<Expr>,
+    env: EnvRef,
+}
+
+impl std::fmt::Debug for Func {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "<lambda ({})>", self.params.join(" "))
+    }
+}
+
+impl PartialEq for Func {
+    // Closures compare by shape; the captured environment is ignored so that
+    // two structurally identical lambdas are considered equal.
+    fn eq(&self, other: &Self) -> bool {
+        self.params == other.params && self.body == other.body
+    }
+}
+
+type EnvRef = Rc<RefCell<Env>>;
+
+/// A lexical scope: a map of bindings plus a pointer to the enclosing scope.
+#[derive(Debug)]
+struct Env {
+    vars: HashMap<String, Value>,
+    parent: Option<EnvRef>,
+}
+
+impl Env {
+    fn new() -> EnvRef {
+        Rc::new(RefCell::new(Env {
+            vars: HashMap::new(),
+            parent: None,
+        }))
+    }
+
+    fn child(parent: &EnvRef) -> EnvRef {
+        Rc::new(RefCell::new(Env {
+            vars: HashMap::new(),
+            parent: Some(parent.clone()),
+        }))
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match self.vars.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self.parent.as_ref().and_then(|p| p.borrow().get(name)),
+        }
+    }
+
+    fn set(&mut self, name: String, value: Value) {
+        self.vars.insert(name, value);
+    }
+}
+
+/// Evaluate `expr` in `env`, returning its value or an error message.
+fn eval(expr: &Expr, env: &EnvRef) -> Result<Value, String> {
+    match expr {
+        Expr::Atom(tok) => eval_atom(tok, env),
+        Expr::List(items) => eval_list(items, env),
+    }
+}
+
+fn eval_atom(tok: &Token, env: &EnvRef) -> Result<Value, String> {
+    match tok {
+        Token::Int(n) => Ok(Value::Int(*n)),
+        Token::Float(f) => Ok(Value::Float(*f)),
+        Token::Str(s) => Ok(Value::Str(s.clone())),
+        Token::Ident(name) => env
+            .borrow()
+            .get(name)
+            .ok_or_else(|| format!("undefined variable: {name}")),
+        Token::LParen | Token::RParen => Err("unexpected paren in expression".to_string()),
+    }
+}
+
+fn eval_list(items: &[Expr], env: &EnvRef) -> Result<Value, String> {
+    let Some((head, rest)) = items.split_first() else {
+        return Ok(Value::Nil);
+    };
+    if let Expr::Atom(Token::Ident(name)) = head {
+        match name.as_str() {
+            "define" => return eval_define(rest, env),
+            "if" => return eval_if(rest, env),
+            "lambda" => return eval_lambda(rest, env),
+            "+" | "-" | "*" | "/" => {
+                let args = eval_args(rest, env)?;
+                return eval_arith(name, &args);
+            }
+            _ => {}
+        }
+    }
+    let func = eval(head, env)?;
+    let args = eval_args(rest, env)?;
+    apply(func, args)
+}
+
+fn eval_args(exprs: &[Expr], env: &EnvRef) -> Result<Vec<Value>, String> {
+    exprs.iter().map(|e| eval(e, env)).collect()
+}
+
+fn apply(func: Value, args: Vec<Value>) -> Result<Value, String> {
+    match func {
+        Value::Func(f) => {
+            if f.params.len() != args.len() {
+                return Err(format!(
+                    "expected {} argument(s), got {}",
+                    f.params.len(),
+                    args.len()
+                ));
+            }
+            let local = Env::child(&f.env);
+            for (param, arg) in f.params.iter().zip(args) {
+                local.borrow_mut().set(param.clone(), arg);
+            }
+            eval(&f.body, &local)
+        }
+        other => Err(format!("not a function: {other:?}")),
+    }
+}
+
+fn eval_define(args: &[Expr], env: &EnvRef) -> Result<Value, String> {
+    let [name, value] = args else {
+        return Err("define expects a name and a value".to_string());
+    };
+    let Expr::Atom(Token::Ident(name)) = name else {
+        return Err("define expects an identifier".to_string());
+    };
+    let value = eval(value, env)?;
+    env.borrow_mut().set(name.clone(), value);
+    Ok(Value::Nil)
+}
+
+fn eval_if(args: &[Expr], env: &EnvRef) -> Result<Value, String> {
+    let (cond, then, otherwise) = match args {
+        [cond, then] => (cond, then, None),
+        [cond, then, otherwise] => (cond, then, Some(otherwise)),
+        _ => return Err("if expects a condition, a consequent, and an optional alternative".to_string()),
+    };
+    if is_truthy(&eval(cond, env)?) {
+        eval(then, env)
+    } else if let Some(otherwise) = otherwise {
+        eval(otherwise, env)
+    } else {
+        Ok(Value::Nil)
+    }
+}
+
+fn eval_lambda(args: &[Expr], env: &EnvRef) -> Result<Value, String> {
+    let [params, body] = args else {
+        return Err("lambda expects a parameter list and a body".to_string());
+    };
+    let Expr::List(params) = params else {
+        return Err("lambda parameter list must be a list".to_string());
+    };
+    let params = params
+        .iter()
+        .map(|p| match p {
+            Expr::Atom(Token::Ident(name)) => Ok(name.clone()),
+            _ => Err("lambda parameters must be identifiers".to_string()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Value::Func(Func {
+        params,
+        body: Box::new(body.clone()),
+        env: env.clone(),
+    }))
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Nil)
+}
+
+fn eval_arith(op: &str, args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err(format!("{op} requires at least one argument"));
+    }
+    if args.iter().all(|v| matches!(v, Value::Int(_))) {
+        let ints: Vec<i64> = args
+            .iter()
+            .map(|v| match v {
+                Value::Int(n) => *n,
+                _ => unreachable!(),
+            })
+            .collect();
+        arith_int(op, &ints).map(Value::Int)
+    } else {
+        let mut floats = Vec::with_capacity(args.len());
+        for v in args {
+            match v {
+                Value::Int(n) => floats.push(*n as f64),
+                Value::Float(f) => floats.push(*f),
+                other => return Err(format!("{op}: expected a number, got {other:?}")),
+            }
+        }
+        Ok(Value::Float(arith_float(op, &floats)))
+    }
+}
+
+fn arith_int(op: &str, xs: &[i64]) -> Result<i64, String> {
+    Ok(match op {
+        "+" => xs.iter().sum(),
+        "*" => xs.iter().product(),
+        "-" if xs.len() == 1 => -xs[0],
+        "-" => xs[1..].iter().fold(xs[0], |acc, x| acc - x),
+        "/" => {
+            let (first, rest) = if xs.len() == 1 { (1, xs) } else { (xs[0], &xs[1..]) };
+            let mut acc = first;
+            for x in rest {
+                if *x == 0 {
+                    return Err("division by zero".to_string());
+                }
+                acc /= x;
+            }
+            acc
+        }
+        _ => unreachable!(),
+    })
+}
+
+fn arith_float(op: &str, xs: &[f64]) -> f64 {
+    match op {
+        "+" => xs.iter().sum(),
+        "*" => xs.iter().product(),
+        "-" if xs.len() == 1 => -xs[0],
+        "-" => xs[1..].iter().fold(xs[0], |acc, x| acc - x),
+        "/" if xs.len() == 1 => 1.0 / xs[0],
+        "/" => xs[1..].iter().fold(xs[0], |acc, x| acc / x),
+        _ => unreachable!(),
     }
 }
 
@@ -82,12 +505,21 @@ enum State {
     Int,
     Float,
     Error,
+    Minus,
+    Comment,
+    String,
+    StringEscape,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Lex `s`, discarding spans, so token-level assertions stay concise.
+    fn lex(s: &'static str) -> Box<dyn Iterator<Item = Result<Token, String>>> {
+        Box::new(TokenIterator::from_str(s).map(|spanned| spanned.result))
+    }
+
     #[test]
     fn token_iterator_new_test() {
         let _it = TokenIterator::new();
@@ -95,7 +527,7 @@ mod tests {
 
     #[test]
     fn token_iterator_test() {
-        let mut it = TokenIterator::from_str("(abc 123 1.3 =)");
+        let mut it = lex("(abc 123 1.3 =)");
         assert_eq!(it.next(), Some(Ok(Token::LParen)));
         assert_eq!(it.next(), Some(Ok(Token::Ident("abc".to_string()))));
         assert_eq!(it.next(), Some(Ok(Token::Int(123))));
@@ -103,7 +535,7 @@ mod tests {
         assert_eq!(it.next(), Some(Err("=".to_string())));
         assert_eq!(it.next(), Some(Ok(Token::RParen)));
         assert_eq!(it.next(), None);
-        it = TokenIterator::from_str("(xyz()");
+        it = lex("(xyz()");
         assert_eq!(it.next(), Some(Ok(Token::LParen)));
         assert_eq!(it.next(), Some(Ok(Token::Ident("xyz".to_string()))));
         assert_eq!(it.next(), Some(Ok(Token::LParen)));
@@ -113,7 +545,7 @@ mod tests {
 
     #[test]
     fn parens_test() {
-        let mut it = TokenIterator::from_str("()(()))()(");
+        let mut it = lex("()(()))()(");
         assert_eq!(it.next(), Some(Ok(Token::LParen)));
         assert_eq!(it.next(), Some(Ok(Token::RParen)));
         assert_eq!(it.next(), Some(Ok(Token::LParen)));
@@ -128,7 +560,7 @@ mod tests {
 
     #[test]
     fn int_test() {
-        let mut it = TokenIterator::from_str("123 1 2 3 456");
+        let mut it = lex("123 1 2 3 456");
         assert_eq!(it.next(), Some(Ok(Token::Int(123))));
         assert_eq!(it.next(), Some(Ok(Token::Int(1))));
         assert_eq!(it.next(), Some(Ok(Token::Int(2))));
@@ -138,7 +570,7 @@ mod tests {
 
     #[test]
     fn float_test() {
-        let mut it = TokenIterator::from_str("1.23 1.55 1.0 9999.3");
+        let mut it = lex("1.23 1.55 1.0 9999.3");
         assert_eq!(it.next(), Some(Ok(Token::Float(1.23))));
         assert_eq!(it.next(), Some(Ok(Token::Float(1.55))));
         assert_eq!(it.next(), Some(Ok(Token::Float(1.0))));
@@ -147,7 +579,7 @@ mod tests {
 
     #[test]
     fn ident_test() {
-        let mut it = TokenIterator::from_str("name a1 snake_case PascalCase _1");
+        let mut it = lex("name a1 snake_case PascalCase _1");
         assert_eq!(it.next(), Some(Ok(Token::Ident("name".to_string()))));
         assert_eq!(it.next(), Some(Ok(Token::Ident("a1".to_string()))));
         assert_eq!(it.next(), Some(Ok(Token::Ident("snake_case".to_string()))));
@@ -155,18 +587,158 @@ mod tests {
         assert_eq!(it.next(), Some(Ok(Token::Ident("_1".to_string()))));
     }
 
+    #[test]
+    fn negative_number_test() {
+        let mut it = lex("-5 -1.5 (- 1 2) -abc");
+        assert_eq!(it.next(), Some(Ok(Token::Int(-5))));
+        assert_eq!(it.next(), Some(Ok(Token::Float(-1.5))));
+        assert_eq!(it.next(), Some(Ok(Token::LParen)));
+        assert_eq!(it.next(), Some(Ok(Token::Ident("-".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::Int(1))));
+        assert_eq!(it.next(), Some(Ok(Token::Int(2))));
+        assert_eq!(it.next(), Some(Ok(Token::RParen)));
+        assert_eq!(it.next(), Some(Ok(Token::Ident("-".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::Ident("abc".to_string()))));
+    }
+
     #[test]
     fn error_test() {
-        let mut it = TokenIterator::from_str("1a 123abc 1.2.3 1.3abc");
+        let mut it = lex("1a 123abc 1.2.3 1.3abc");
         assert_eq!(it.next(), Some(Err("1a".to_string())));
         assert_eq!(it.next(), Some(Err("123abc".to_string())));
         assert_eq!(it.next(), Some(Err("1.2.3".to_string())));
         assert_eq!(it.next(), Some(Err("1.3abc".to_string())));
     }
 
+    #[test]
+    fn string_test() {
+        let mut it = lex(r#"("hello" "a\tb\n" "say \"hi\"" "\\")"#);
+        assert_eq!(it.next(), Some(Ok(Token::LParen)));
+        assert_eq!(it.next(), Some(Ok(Token::Str("hello".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::Str("a\tb\n".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::Str("say \"hi\"".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::Str("\\".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::RParen)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn unterminated_string_test() {
+        let mut it = lex("\"no end");
+        assert!(matches!(it.next(), Some(Err(_))));
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut it = TokenIterator::from_str("(add 1 (mul 2 3))");
+        let expr = parse(&mut it).unwrap();
+        assert_eq!(
+            expr,
+            Expr::List(vec![
+                Expr::Atom(Token::Ident("add".to_string())),
+                Expr::Atom(Token::Int(1)),
+                Expr::List(vec![
+                    Expr::Atom(Token::Ident("mul".to_string())),
+                    Expr::Atom(Token::Int(2)),
+                    Expr::Atom(Token::Int(3)),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_atom_test() {
+        let mut it = TokenIterator::from_str("42");
+        assert_eq!(parse(&mut it), Ok(Expr::Atom(Token::Int(42))));
+    }
+
+    #[test]
+    fn parse_unbalanced_test() {
+        let mut it = TokenIterator::from_str("(add 1");
+        assert!(parse(&mut it).is_err());
+        let mut it = TokenIterator::from_str(")");
+        assert!(parse(&mut it).is_err());
+    }
+
+    fn eval_str(s: &'static str) -> Result<Value, String> {
+        let mut it = TokenIterator::from_str(s);
+        let env = Env::new();
+        eval(&parse(&mut it)?, &env)
+    }
+
+    #[test]
+    fn eval_arith_test() {
+        assert_eq!(eval_str("(+ 1 2 3)"), Ok(Value::Int(6)));
+        assert_eq!(eval_str("(- 10 3 2)"), Ok(Value::Int(5)));
+        assert_eq!(eval_str("(* 2 3 4)"), Ok(Value::Int(24)));
+        assert_eq!(eval_str("(+ 1 2.5)"), Ok(Value::Float(3.5)));
+        assert_eq!(eval_str("(/ 1 0)"), Err("division by zero".to_string()));
+    }
+
+    #[test]
+    fn eval_define_test() {
+        let mut it = TokenIterator::from_str("(define x 10) (+ x 5)");
+        let env = Env::new();
+        eval(&parse(&mut it).unwrap(), &env).unwrap();
+        assert_eq!(eval(&parse(&mut it).unwrap(), &env), Ok(Value::Int(15)));
+    }
+
+    #[test]
+    fn eval_if_test() {
+        assert_eq!(eval_str("(if 1 10 20)"), Ok(Value::Int(10)));
+        assert_eq!(eval_str("(if () 10 20)"), Ok(Value::Int(20)));
+    }
+
+    #[test]
+    fn eval_lambda_test() {
+        assert_eq!(eval_str("((lambda (x y) (+ x y)) 3 4)"), Ok(Value::Int(7)));
+    }
+
+    #[test]
+    fn eval_closure_test() {
+        let mut it = TokenIterator::from_str("(define add (lambda (x) (lambda (y) (+ x y)))) ((add 2) 5)");
+        let env = Env::new();
+        eval(&parse(&mut it).unwrap(), &env).unwrap();
+        assert_eq!(eval(&parse(&mut it).unwrap(), &env), Ok(Value::Int(7)));
+    }
+
+    #[test]
+    fn comment_test() {
+        // Full-line comment followed by a real expression.
+        let mut it = lex("; a leading note\n(a)");
+        assert_eq!(it.next(), Some(Ok(Token::LParen)));
+        assert_eq!(it.next(), Some(Ok(Token::Ident("a".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::RParen)));
+        assert_eq!(it.next(), None);
+        // Trailing comment between tokens.
+        let mut it = lex("(add 1 ; a note\n 2)");
+        assert_eq!(it.next(), Some(Ok(Token::LParen)));
+        assert_eq!(it.next(), Some(Ok(Token::Ident("add".to_string()))));
+        assert_eq!(it.next(), Some(Ok(Token::Int(1))));
+        assert_eq!(it.next(), Some(Ok(Token::Int(2))));
+        assert_eq!(it.next(), Some(Ok(Token::RParen)));
+        assert_eq!(it.next(), None);
+        // Comment at end of input with no trailing newline yields no token.
+        let mut it = lex("; just a comment");
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn span_test() {
+        let mut it = TokenIterator::from_str("(add\n  12)");
+        // "(" at byte 0, line 1, col 1
+        assert_eq!(it.next().unwrap().span, Span { start: 0, end: 1, line: 1, col: 1 });
+        // "add" spans bytes 1..4 on line 1
+        assert_eq!(it.next().unwrap().span, Span { start: 1, end: 4, line: 1, col: 2 });
+        // "12" starts on line 2 after two leading spaces
+        assert_eq!(it.next().unwrap().span, Span { start: 7, end: 9, line: 2, col: 3 });
+        // ")" immediately follows
+        assert_eq!(it.next().unwrap().span, Span { start: 9, end: 10, line: 2, col: 5 });
+    }
+
     #[test]
     fn letters_after_numbers_test() {
-        let mut it = TokenIterator::from_str("(123 123abc abc)");
+        let mut it = lex("(123 123abc abc)");
         assert_eq!(it.next(), Some(Ok(Token::LParen)));
         assert_eq!(it.next(), Some(Ok(Token::Int(123))));
         assert_eq!(it.next(), Some(Err("123abc".to_string())));
@@ -176,7 +748,10 @@ mod tests {
 }
 
 fn main() {
-    for token in TokenIterator::new() {
-        println!("{:?}", token);
+    let mut it = TokenIterator::new();
+    let env = Env::new();
+    match parse(&mut it).and_then(|expr| eval(&expr, &env)) {
+        Ok(value) => println!("{value:?}"),
+        Err(e) => eprintln!("error: {e}"),
     }
 }